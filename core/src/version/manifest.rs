@@ -0,0 +1,39 @@
+// Borrowed from the `version-compare` crate's `VersionManifest` idea: a small config
+// object callers can pass when constructing a `VersionSpec` to control how
+// the input string gets tokenized, without changing the default parsing behavior.
+
+/// Configures how version strings are parsed.
+///
+/// Passing `None` anywhere a `&VersionManifest` is expected must produce output that is
+/// byte-for-byte identical to parsing with the crate's hard-coded defaults.
+///
+/// This intentionally only covers what's actually wired into parsing today
+/// (`separators`). Fields for `Version`'s comparison routines (e.g. ignoring a `+`
+/// local-identifier segment, capping the number of significant components) belong here
+/// once that comparison-side wiring exists - don't add them back as inert config ahead
+/// of that.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VersionManifest {
+    /// Characters that separate version components. Defaults to `.`, `+`, `-`, `_`.
+    ///
+    /// Only `.` and `+` have an effect: in a wildcard spec such as `1.*.2`, each of the
+    /// two that is listed here is matched as a literal character, and one that is not
+    /// listed is passed to the regex unescaped. `-` and `_` are accepted but change nothing.
+    pub separators: Vec<char>,
+}
+
+impl Default for VersionManifest {
+    fn default() -> Self {
+        VersionManifest {
+            separators: vec!['.', '+', '-', '_'],
+        }
+    }
+}
+
+impl VersionManifest {
+    pub fn new() -> Self { Default::default() }
+
+    pub fn is_separator(&self, c: char) -> bool {
+        self.separators.contains(&c)
+    }
+}
@@ -1,8 +1,10 @@
 use super::spec_trees::*;
 use regex::Regex;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use crate::{Version, CompOp};
+use super::manifest::VersionManifest;
 
 pub trait Spec {
     // properties in Python
@@ -29,7 +31,25 @@ struct VersionSpec {
 }
 
 impl Spec for VersionSpec {
-    fn merge(&self, other: &Self) -> Self { panic!("Not implemented") }
+    fn merge(&self, other: &Self) -> Self {
+        // Mirrors semver's VersionReq, which accumulates a Vec<Predicate> that all must hold:
+        // MatchAlways is the merge identity, MatchNever is the merge absorbing element.
+        if matches!(self.matcher, MatchEnum::MatchAlways(_)) { return other.clone() }
+        if matches!(other.matcher, MatchEnum::MatchAlways(_)) { return self.clone() }
+        if matches!(self.matcher, MatchEnum::MatchNever(_)) { return self.clone() }
+        if matches!(other.matcher, MatchEnum::MatchNever(_)) { return other.clone() }
+
+        let as_part = |spec: &VersionSpec| match &spec.tree {
+            Some(tree) => ConstraintTreePart::Tree(tree.clone()),
+            None => ConstraintTreePart::Constraint(spec.spec_str.clone()),
+        };
+        let tree = ConstraintTree {
+            combinator: Combinator::And,
+            parts: vec![as_part(self), as_part(other)],
+        };
+        // Re-normalizes spec_str via untreeify and rebuilds the matcher as a MatchAll.
+        tree.into()
+    }
     fn get_spec(&self) -> &str { &self.spec_str }
     fn get_matcher(&self) -> &MatchEnum { &self.matcher }
     fn is_exact(&self) -> bool { self._is_exact }
@@ -39,56 +59,119 @@ impl TryFrom<&str> for VersionSpec {
     type Error = String;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
+        VersionSpec::try_from_with_manifest(input, None)
+    }
+}
+
+impl VersionSpec {
+    /// Same as `TryFrom<&str>`, but accepts an optional `VersionManifest` to control
+    /// the component separators used during parsing. Passing `None` is byte-for-byte
+    /// identical to parsing with the crate's hard-coded defaults.
+    pub fn try_from_with_manifest(input: &str, manifest: Option<&VersionManifest>) -> Result<Self, String> {
         lazy_static! { static ref REGEX_SPLIT_RE: Regex = Regex::new( r#".*[()|,^$]"# ).unwrap(); }
-        lazy_static! { static ref OPERATOR_START: HashSet<&'static str> = ["=", "<", ">", "!", "~"].iter().cloned().collect(); }
-        let _is_exact = false;
-        let split_input: Vec<&str> = REGEX_SPLIT_RE.split(input).collect();
-        if split_input.len() > 0 {
+        // `REGEX_SPLIT_RE.split(input)` always yields at least one element, even for
+        // non-compound input, so the compound/leaf branch must be chosen by `is_match`,
+        // not by the split result's length.
+        if REGEX_SPLIT_RE.is_match(input) {
             let tree = treeify(input)?;
-            return Ok(tree.into());
-        }
-        let mut matcher: MatchEnum = Default::default();
-        let mut _is_exact = false;
-        if input.starts_with("^") || input.ends_with("$") {
-            if ! input.starts_with("^") || ! input.ends_with("$") {
-                return Err(format!("regex specs must start with '^' and end with '$' - spec '{}' is incorrect", input))
-            }
-            matcher = MatchRegex { expression: Regex::new(input).unwrap() }.into();
-            _is_exact = false;
-        } else if OPERATOR_START.contains(&input[..1]) {
-            let (_m, _e) = create_match_enum_from_operator_str(input)?;
-            matcher = _m;
-            _is_exact = _e;
-        } else if input == "*" {
-            matcher = MatchAlways {}.into();
-            _is_exact = false;
-        } else if input.trim_end_matches("*").contains("*") {
-            let rx = input.replace(".", r"\.").replace("+", r"\+").replace("*", r".*");
-            let rx: Regex = Regex::new(&format!(r"^(?:{})$", rx)).unwrap();
-            matcher = MatchRegex { expression: rx }.into();
-            _is_exact = false;
-        } else if input.ends_with("*") {
-            matcher = MatchOperator {
-                operator: CompOp::StartsWith,
-                version: input.trim_end_matches(|c| c=='*' || c=='.').into() }.into();
-            _is_exact = false;
-        } else if ! input.contains("@") {
-            matcher = MatchOperator {operator: CompOp::Eq, version: input.into()}.into();
-            _is_exact = true;
-        } else {
-            matcher = MatchExact { spec: input.to_string() }.into();
-            _is_exact = true;
+            // `treeify` only validates structure (commas/pipes/parens), not that each
+            // leaf is itself a well-formed constraint, so building the matcher can still
+            // fail here (e.g. ">=1.7,!!bad") and the error must propagate, not panic.
+            let matcher = build_tree_matcher(&tree, manifest)?;
+            let spec_str = untreeify(&tree).unwrap();
+            // ConstraintTree matches are never exact
+            return Ok(VersionSpec { spec_str, tree: Some(tree), matcher, _is_exact: false });
         }
+        let (matcher, _is_exact) = build_leaf_matcher(input, manifest)?;
         Ok(VersionSpec { spec_str: input.to_string(), tree: None, matcher, _is_exact })
     }
 }
 
+// A `serde` feature for VersionSpec (serializing/deserializing as the canonical spec
+// string, the same way semver's `VersionReq` round-trips) was attempted here, but this
+// tree has no Cargo.toml to declare the optional `serde` dependency or a `[features]
+// serde = [...]` entry - without that, `--features serde` can never be requested by any
+// consumer and a `#[cfg(feature = "serde")]` impl would be permanently dead code. Held
+// until the manifest-side wiring can land alongside it.
+
+/// Parses a single, non-compound constraint string (no `,`/`|`/`(`/`)`) into a matcher.
+/// This is the logic shared between `TryFrom<&str>` and the leaves of a `ConstraintTree`.
+fn build_leaf_matcher(input: &str, manifest: Option<&VersionManifest>) -> Result<(MatchEnum, bool), String> {
+    lazy_static! { static ref OPERATOR_START: HashSet<&'static str> = ["=", "<", ">", "!", "~"].iter().cloned().collect(); }
+    let default_manifest = VersionManifest::default();
+    let manifest = manifest.unwrap_or(&default_manifest);
+    let matcher: MatchEnum;
+    let _is_exact: bool;
+    if input.starts_with("^") || input.ends_with("$") {
+        if ! input.starts_with("^") || ! input.ends_with("$") {
+            return Err(format!("regex specs must start with '^' and end with '$' - spec '{}' is incorrect", input))
+        }
+        matcher = MatchRegex { expression: Regex::new(input).unwrap() }.into();
+        _is_exact = false;
+    } else if OPERATOR_START.contains(&input[..1]) {
+        let (_m, _e) = create_match_enum_from_operator_str(input)?;
+        matcher = _m;
+        _is_exact = _e;
+    } else if input == "*" {
+        matcher = MatchAlways {}.into();
+        _is_exact = false;
+    } else if input.trim_end_matches("*").contains("*") {
+        let mut rx = input.to_string();
+        for sep in manifest.separators.iter().filter(|&&c| c == '.' || c == '+') {
+            rx = rx.replace(*sep, &format!("\\{}", sep));
+        }
+        let rx = rx.replace("*", r".*");
+        let rx: Regex = Regex::new(&format!(r"^(?:{})$", rx)).unwrap();
+        matcher = MatchRegex { expression: rx }.into();
+        _is_exact = false;
+    } else if input.ends_with("*") {
+        matcher = MatchOperator {
+            operator: CompOp::StartsWith,
+            version: input.trim_end_matches(|c| c=='*' || c=='.').into() }.into();
+        _is_exact = false;
+    } else if ! input.contains("@") {
+        matcher = MatchOperator {operator: CompOp::Eq, version: input.into()}.into();
+        _is_exact = true;
+    } else {
+        matcher = MatchExact { spec: input.to_string() }.into();
+        _is_exact = true;
+    }
+    Ok((matcher, _is_exact))
+}
+
+/// Builds the matcher for one `ConstraintTree` part: a leaf is parsed with
+/// `build_leaf_matcher`, and a nested tree recurses via `build_tree_matcher`. `manifest`
+/// is threaded through every level of the recursion so a nested leaf parses under the
+/// same separators as the spec's top level.
+///
+/// `treeify` only validates the tree's structure (commas/pipes/parens); it does not
+/// validate that each leaf is itself a well-formed constraint, so this can fail on a
+/// structurally-valid tree with a malformed leaf (e.g. `">=1.7,!!bad"`) and must return
+/// the error rather than panic.
+fn build_part_matcher(part: &ConstraintTreePart, manifest: Option<&VersionManifest>) -> Result<MatchEnum, String> {
+    match part {
+        ConstraintTreePart::Constraint(leaf) => build_leaf_matcher(leaf, manifest).map(|(m, _)| m),
+        ConstraintTreePart::Tree(subtree) => build_tree_matcher(subtree, manifest),
+    }
+}
+
+fn build_tree_matcher(tree: &ConstraintTree, manifest: Option<&VersionManifest>) -> Result<MatchEnum, String> {
+    let matchers: Result<Vec<MatchEnum>, String> =
+        tree.parts.iter().map(|p| build_part_matcher(p, manifest)).collect();
+    let matchers = matchers?;
+    Ok(match tree.combinator {
+        Combinator::Or => MatchAny { matchers }.into(),
+        _ => MatchAll { matchers }.into(),
+    })
+}
+
 impl From<ConstraintTree> for VersionSpec {
     fn from(tree: ConstraintTree) -> VersionSpec {
-        let matcher = match tree.combinator {
-            Combinator::Or => MatchAny { tree: tree.clone() }.into(),
-            _ => MatchAll { tree: tree.clone() }.into()
-        };
+        // Only reached via `merge`, which builds this tree out of parts taken from
+        // already-successfully-parsed `VersionSpec`s - their leaves were validated once
+        // already, so re-parsing them here cannot fail.
+        let matcher = build_tree_matcher(&tree, None)
+            .expect("merge only combines leaves from already-validated VersionSpecs");
         let spec_str = untreeify(&tree).unwrap();
         // ConstraintTree matches are never exact
         VersionSpec { spec_str, tree: Some(tree), matcher, _is_exact: false }
@@ -96,7 +179,9 @@ impl From<ConstraintTree> for VersionSpec {
 }
 
 fn create_match_enum_from_operator_str(input: &str) -> Result<(MatchEnum, bool), String> {
-    lazy_static! { static ref VERSION_RELATION_RE: Regex = Regex::new( r#"^(=|==|!=|<=|>=|<|>|~=)(?![=<>!~])(\S+)$"# ).unwrap(); }
+    // The `regex` crate has no look-ahead, so the operand's first character is what rules
+    // out a doubled operator such as "=<1.0" or "====1.0".
+    lazy_static! { static ref VERSION_RELATION_RE: Regex = Regex::new( r#"^(===|=|==|!=|<=|>=|<|>|~=)([^=<>!~\s]\S*)$"# ).unwrap(); }
 
     let (mut operator_str, mut v_str) = match VERSION_RELATION_RE.captures(input) {
         None => return Err(format!("invalid operator in string {}", input)),
@@ -108,9 +193,16 @@ fn create_match_enum_from_operator_str(input: &str) -> Result<(MatchEnum, bool),
             operator_str = "!=startswith";
         } else if operator_str == "~=" {
             return Err(format!("invalid operator (~=) with '.*' in spec string: {}", input));
+        } else if operator_str == "===" {
+            // Arbitrary equality is a literal string comparison; it has no wildcard form.
+            return Err(format!("invalid operator (===) with '.*' in spec string: {}", input));
         }
         v_str = &v_str[..v_str.len()-2];
     }
+    if operator_str == "===" {
+        let matcher = MatchArbitraryEqual { version: v_str.to_string() };
+        return Ok((matcher.into(), true));
+    }
     let matcher = MatchOperator { operator: CompOp::from_sign(operator_str).unwrap(), version: v_str.into() };
     let _is_exact = operator_str == "==";
     Ok((matcher.into(), _is_exact))
@@ -126,6 +218,7 @@ enum MatchEnum {
     MatchAlways,
     MatchExact,
     MatchNever,
+    MatchArbitraryEqual,
 }
 
 impl Default for MatchEnum {
@@ -139,27 +232,27 @@ trait MatchFn {
 
 #[derive(Clone)]
 struct MatchAny {
-    tree: ConstraintTree,
+    // Compiled once at construction time (see `build_tree_matcher`) so `test` never
+    // re-parses the tree's leaf constraints.
+    matchers: Vec<MatchEnum>,
 }
 impl MatchFn for MatchAny {
     fn test(&self, other: &str) -> bool {
-        // We probably need to convert each individual string of a ConstraintTree into a
-        // MatchOperator, and then have the "other" match with each of those individually.
-        panic!("Not implemented.  Not sure how tuple of VersionSpec matches with ConstraintTree")
-        // self.tree.parts.iter().any(|x| x == other)
+        // An empty Or tree matches nothing, mirroring MatchNever.
+        self.matchers.iter().any(|m| m.test(other))
     }
 }
 
 #[derive(Clone)]
 struct MatchAll {
-    tree: ConstraintTree,
+    // Compiled once at construction time (see `build_tree_matcher`) so `test` never
+    // re-parses the tree's leaf constraints.
+    matchers: Vec<MatchEnum>,
 }
 impl MatchFn for MatchAll {
     fn test(&self, other: &str) -> bool {
-        // We probably need to convert each individual string of a ConstraintTree into a
-        // MatchOperator, and then have the "other" match with each of those individually.
-        panic!("Not implemented.  Not sure how tuple of VersionSpec matches with ConstraintTree")
-        // self.tree.parts.iter().all(|x| x == other)
+        // An empty And tree matches everything, mirroring MatchAlways.
+        self.matchers.iter().all(|m| m.test(other))
     }
 }
 
@@ -210,6 +303,273 @@ impl MatchFn for MatchExact {
     }
 }
 
+// PEP 440's `===` arbitrary-equality operator: a direct, non-normalized string
+// comparison with no component padding or numeric coercion, e.g. `===1.0` matches only
+// the literal `1.0`.
+#[derive(Clone)]
+struct MatchArbitraryEqual {
+    version: String
+}
+impl MatchFn for MatchArbitraryEqual {
+    fn test(&self, other: & str) -> bool {
+        other == self.version
+    }
+}
+
+
+// --- subset/containment queries -------------------------------------------------
+//
+// Answers "does every version satisfying `other` also satisfy `self`?" by normalizing
+// both specs' matchers into interval form over the crate's Version ordering, then
+// comparing the interval sets. Matchers with no interval representation (regex, exact
+// hash matches, `===`) fall back to comparing spec strings for equality.
+
+/// A bound on one side of an interval. `Included`/`Excluded` carry the raw version
+/// string rather than a parsed `Version`, so ordering between two bounds can reuse the
+/// crate's own `compare_to_str` semantics (prerelease/local suffixes and all) via
+/// `version_cmp` instead of requiring `Version` to implement `Ord` itself.
+#[derive(Clone, Debug)]
+enum Bound {
+    NegInf,
+    Included(String),
+    Excluded(String),
+    PosInf,
+}
+
+#[derive(Clone, Debug)]
+struct Interval {
+    lower: Bound,
+    upper: Bound,
+}
+
+fn version_cmp(a: &str, b: &str) -> Ordering {
+    // `MatchOperator { operator, version }.test(other)` evaluates `other OP version`, so
+    // `Lt { version: a }.test(b)` asks "is b < a", i.e. a > b - that's the Greater arm.
+    if a == b || MatchOperator { operator: CompOp::Eq, version: a.into() }.test(b) {
+        Ordering::Equal
+    } else if (MatchOperator { operator: CompOp::Lt, version: a.into() }).test(b) {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    }
+}
+
+/// Orders a bound's position on the version line. `as_lower` picks which side of an
+/// excluded endpoint it represents: v+epsilon when the bound starts an interval, v-epsilon
+/// when it ends one, so touching-but-disjoint bounds compare correctly either way.
+fn bound_rank(bound: &Bound, as_lower: bool) -> (i8, Option<String>, i8) {
+    match bound {
+        Bound::NegInf => (0, None, 0),
+        Bound::PosInf => (2, None, 0),
+        Bound::Included(v) => (1, Some(v.clone()), 0),
+        Bound::Excluded(v) => (1, Some(v.clone()), if as_lower { 1 } else { -1 }),
+    }
+}
+
+fn rank_cmp(a: &(i8, Option<String>, i8), b: &(i8, Option<String>, i8)) -> Ordering {
+    match a.0.cmp(&b.0) {
+        Ordering::Equal => {}
+        other => return other,
+    }
+    match (&a.1, &b.1) {
+        (Some(av), Some(bv)) => match version_cmp(av, bv) {
+            Ordering::Equal => a.2.cmp(&b.2),
+            other => other,
+        },
+        _ => a.2.cmp(&b.2),
+    }
+}
+
+fn lower_rank(i: &Interval) -> (i8, Option<String>, i8) { bound_rank(&i.lower, true) }
+fn upper_rank(i: &Interval) -> (i8, Option<String>, i8) { bound_rank(&i.upper, false) }
+
+fn intervals_overlap(a: &Interval, b: &Interval) -> bool {
+    rank_cmp(&lower_rank(a), &upper_rank(b)) != Ordering::Greater
+        && rank_cmp(&lower_rank(b), &upper_rank(a)) != Ordering::Greater
+}
+
+fn boundary_value(bound: &Bound) -> Option<&str> {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(v.as_str()),
+        _ => None,
+    }
+}
+
+// Two intervals with no real gap between them (e.g. `(.., 1.0)` and `[1.0, ..)`) should
+// still merge into one during normalization, even though their epsilon ranks don't overlap.
+fn touches(a: &Interval, b: &Interval) -> bool {
+    match (boundary_value(&a.upper), boundary_value(&b.lower)) {
+        (Some(av), Some(bv)) if version_cmp(av, bv) == Ordering::Equal => {
+            matches!(a.upper, Bound::Included(_)) || matches!(b.lower, Bound::Included(_))
+        }
+        _ => false,
+    }
+}
+
+fn normalize(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|i| rank_cmp(&lower_rank(i), &upper_rank(i)) != Ordering::Greater);
+    intervals.sort_by(|a, b| rank_cmp(&lower_rank(a), &lower_rank(b)));
+    let mut merged: Vec<Interval> = Vec::new();
+    for iv in intervals {
+        if let Some(last) = merged.last_mut() {
+            if intervals_overlap(last, &iv) || touches(last, &iv) {
+                if rank_cmp(&upper_rank(&iv), &upper_rank(last)) == Ordering::Greater {
+                    last.upper = iv.upper;
+                }
+                continue;
+            }
+        }
+        merged.push(iv);
+    }
+    merged
+}
+
+fn union(a: Vec<Interval>, b: Vec<Interval>) -> Vec<Interval> {
+    let mut all = a;
+    all.extend(b);
+    normalize(all)
+}
+
+fn intersect(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let mut out = Vec::new();
+    for x in a {
+        for y in b {
+            if intervals_overlap(x, y) {
+                let lower = if rank_cmp(&lower_rank(x), &lower_rank(y)) == Ordering::Less { y.lower.clone() } else { x.lower.clone() };
+                let upper = if rank_cmp(&upper_rank(x), &upper_rank(y)) == Ordering::Greater { y.upper.clone() } else { x.upper.clone() };
+                out.push(Interval { lower, upper });
+            }
+        }
+    }
+    normalize(out)
+}
+
+fn complement_of(bound: &Bound) -> Bound {
+    match bound {
+        Bound::Included(v) => Bound::Excluded(v.clone()),
+        Bound::Excluded(v) => Bound::Included(v.clone()),
+        Bound::NegInf => Bound::NegInf,
+        Bound::PosInf => Bound::PosInf,
+    }
+}
+
+fn subtract(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let mut remaining = a.to_vec();
+    for cut in b {
+        let mut next = Vec::new();
+        for iv in remaining {
+            if !intervals_overlap(&iv, cut) {
+                next.push(iv);
+                continue;
+            }
+            if rank_cmp(&lower_rank(&iv), &lower_rank(cut)) == Ordering::Less {
+                next.push(Interval { lower: iv.lower.clone(), upper: complement_of(&cut.lower) });
+            }
+            if rank_cmp(&upper_rank(&iv), &upper_rank(cut)) == Ordering::Greater {
+                next.push(Interval { lower: complement_of(&cut.upper), upper: iv.upper.clone() });
+            }
+        }
+        remaining = next;
+    }
+    normalize(remaining)
+}
+
+// Computes the half-open upper bound for a wildcard/StartsWith prefix, e.g. "1.7" -> "1.8",
+// by bumping the last numeric component. A non-numeric last component has no well-defined
+// "next" prefix, so the range is left unbounded above.
+fn next_prefix(prefix: &str) -> Option<String> {
+    let mut parts: Vec<String> = prefix.split('.').map(|s| s.to_string()).collect();
+    let last = parts.pop()?;
+    let bumped = last.parse::<u64>().ok()? + 1;
+    parts.push(bumped.to_string());
+    Some(parts.join("."))
+}
+
+// PEP 440 compatible-release upper bound: `~=3.3.2` means `>=3.3.2, ==3.3.*`, i.e. the
+// last release segment is dropped and the new last segment is bumped.
+fn compatible_upper_bound(version: &str) -> Option<String> {
+    let mut parts: Vec<String> = version.split('.').map(|s| s.to_string()).collect();
+    if parts.len() < 2 { return None }
+    parts.pop();
+    next_prefix(&parts.join("."))
+}
+
+fn operator_intervals(op: &MatchOperator) -> Option<Vec<Interval>> {
+    let v = op.version.to_string();
+    match op.operator {
+        CompOp::Eq => Some(vec![Interval { lower: Bound::Included(v.clone()), upper: Bound::Included(v) }]),
+        CompOp::Ne => Some(vec![
+            Interval { lower: Bound::NegInf, upper: Bound::Excluded(v.clone()) },
+            Interval { lower: Bound::Excluded(v), upper: Bound::PosInf },
+        ]),
+        CompOp::Lt => Some(vec![Interval { lower: Bound::NegInf, upper: Bound::Excluded(v) }]),
+        CompOp::Le => Some(vec![Interval { lower: Bound::NegInf, upper: Bound::Included(v) }]),
+        CompOp::Gt => Some(vec![Interval { lower: Bound::Excluded(v), upper: Bound::PosInf }]),
+        CompOp::Ge => Some(vec![Interval { lower: Bound::Included(v), upper: Bound::PosInf }]),
+        CompOp::StartsWith => {
+            let upper = match next_prefix(&v) { Some(n) => Bound::Excluded(n), None => Bound::PosInf };
+            Some(vec![Interval { lower: Bound::Included(v), upper }])
+        }
+        CompOp::Compatible => {
+            let upper = match compatible_upper_bound(&v) { Some(n) => Bound::Excluded(n), None => Bound::PosInf };
+            Some(vec![Interval { lower: Bound::Included(v), upper }])
+        }
+        // `!=X.*` is the complement of the `StartsWith` range: everything below the
+        // prefix, plus everything from the prefix's upper bound onward (if any).
+        CompOp::NotStartsWith => {
+            let mut out = vec![Interval { lower: Bound::NegInf, upper: Bound::Excluded(v.clone()) }];
+            if let Some(n) = next_prefix(&v) {
+                out.push(Interval { lower: Bound::Included(n), upper: Bound::PosInf });
+            }
+            Some(out)
+        }
+        // Any other operator has no interval form here and falls back to the
+        // spec-string-equality comparison in `contains`/`is_disjoint`. (`===` never
+        // reaches this: it builds a `MatchArbitraryEqual`, handled in `matcher_intervals`.)
+        _ => None,
+    }
+}
+
+fn matcher_intervals(matcher: &MatchEnum) -> Option<Vec<Interval>> {
+    match matcher {
+        MatchEnum::MatchAlways(_) => Some(vec![Interval { lower: Bound::NegInf, upper: Bound::PosInf }]),
+        MatchEnum::MatchNever(_) => Some(vec![]),
+        MatchEnum::MatchOperator(op) => operator_intervals(op),
+        MatchEnum::MatchAny(any) => {
+            let mut acc = Vec::new();
+            for m in &any.matchers { acc = union(acc, matcher_intervals(m)?); }
+            Some(acc)
+        }
+        MatchEnum::MatchAll(all) => {
+            let mut acc = vec![Interval { lower: Bound::NegInf, upper: Bound::PosInf }];
+            for m in &all.matchers { acc = intersect(&acc, &matcher_intervals(m)?); }
+            Some(acc)
+        }
+        MatchEnum::MatchRegex(_) | MatchEnum::MatchExact(_) | MatchEnum::MatchArbitraryEqual(_) => None,
+    }
+}
+
+impl VersionSpec {
+    fn intervals(&self) -> Option<Vec<Interval>> {
+        matcher_intervals(&self.matcher)
+    }
+
+    /// Does every version satisfying `other` also satisfy `self`?
+    pub fn contains(&self, other: &VersionSpec) -> bool {
+        match (self.intervals(), other.intervals()) {
+            (Some(mine), Some(theirs)) => subtract(&theirs, &mine).is_empty(),
+            _ => self.spec_str == other.spec_str,
+        }
+    }
+
+    /// Is there no version that satisfies both `self` and `other`?
+    pub fn is_disjoint(&self, other: &VersionSpec) -> bool {
+        match (self.intervals(), other.intervals()) {
+            (Some(mine), Some(theirs)) => intersect(&mine, &theirs).is_empty(),
+            _ => self.spec_str != other.spec_str,
+        }
+    }
+}
 
 #[cfg_attr(tarpaulin, skip)]
 #[cfg(test)]
@@ -244,6 +604,33 @@ mod tests {
         assert_eq!(VersionSpec::try_from("1.2.3+4.5.6").unwrap().test_match("1.2.4+5*"), false);
     }
 
+    #[test]
+    fn test_match_any_and_match_all_recursion() {
+        // An Or tree (MatchAny) matches if any branch matches.
+        let any = VersionSpec::try_from("1.7.*|2.0.*").unwrap();
+        assert!(any.test_match("1.7.5"));
+        assert!(any.test_match("2.0.1"));
+        assert_eq!(any.test_match("1.8.0"), false);
+
+        // An And tree (MatchAll) matches only if every branch matches.
+        let all = VersionSpec::try_from(">=1.7,<2.0").unwrap();
+        assert!(all.test_match("1.7.5"));
+        assert_eq!(all.test_match("2.0.1"), false);
+
+        // Nested trees recurse through both MatchAny and MatchAll.
+        let nested = VersionSpec::try_from("(>=1.7,<1.8)|(>=2.0,<2.1)").unwrap();
+        assert!(nested.test_match("1.7.9"));
+        assert!(nested.test_match("2.0.5"));
+        assert_eq!(nested.test_match("1.9.0"), false);
+    }
+
+    #[test]
+    fn test_malformed_leaf_in_compound_spec_errors() {
+        // Structurally this splits fine on the comma; the second leaf is what's invalid,
+        // and that must come back as an Err, not a panic.
+        assert!(VersionSpec::try_from(">=1.7,!!bad").is_err());
+    }
+
     #[test]
     fn test_ver_eval_errors() {
         // each of these should raise
@@ -357,6 +744,21 @@ mod tests {
         self.assertTrue(m.match (version))
     }
 
+    #[test]
+    fn test_merge() {
+        let lower = VersionSpec::try_from(">=1.7").unwrap();
+        let upper = VersionSpec::try_from("<2.0").unwrap();
+        let merged = lower.merge(&upper);
+        assert!(merged.test_match("1.8.0"));
+        assert_eq!(merged.test_match("2.0.0"), false);
+        assert_eq!(merged.test_match("1.6.0"), false);
+
+        // MatchAlways is the merge identity in both directions.
+        let always = VersionSpec::try_from("*").unwrap();
+        assert_eq!(always.merge(&lower).get_spec(), lower.get_spec());
+        assert_eq!(lower.merge(&always).get_spec(), lower.get_spec());
+    }
+
     #[test]
     fn test_not_eq_star() {
         assert_eq!(VersionSpec::try_from("=3.3").unwrap().test_match("3.3.1"), true);
@@ -431,4 +833,58 @@ mod tests {
             _ => true
         };
      }
+
+    #[test]
+    fn test_arbitrary_equality_matches() {
+        // === is a literal string comparison: no component padding, no numeric
+        // coercion, no prerelease/local handling.
+        assert!(VersionSpec::try_from("===1.0").unwrap().test_match("1.0"));
+        assert_eq!(VersionSpec::try_from("===1.0").unwrap().test_match("1.0.0"), false);
+        assert_eq!(VersionSpec::try_from("===1.0").unwrap().test_match("1.0.post1"), false);
+    }
+
+    #[test]
+    fn test_try_from_with_manifest() {
+        // `None` must parse byte-for-byte the same as the crate's hard-coded defaults.
+        let default_manifest = VersionManifest::default();
+        assert_eq!(
+            VersionSpec::try_from("1.7.*").unwrap().test_match("1.7.2"),
+            VersionSpec::try_from_with_manifest("1.7.*", Some(&default_manifest)).unwrap().test_match("1.7.2"),
+        );
+
+        // A custom manifest must be honored by a wildcard leaf even when that leaf is
+        // nested inside a compound (comma/pipe) spec, not just at the top level. With the
+        // default manifest, '.' is escaped to a literal dot; dropping it from
+        // `separators` leaves '.' as the regex metacharacter (matches any one char), so a
+        // spec like "1.*.2" becomes far more permissive.
+        let no_dot_manifest = VersionManifest { separators: vec!['+'], ..VersionManifest::default() };
+        let nested = VersionSpec::try_from_with_manifest(">=1.0, 1.*.2", Some(&no_dot_manifest)).unwrap();
+        assert!(nested.test_match("1X5X2"));
+        let nested_default = VersionSpec::try_from(">=1.0, 1.*.2").unwrap();
+        assert!(!nested_default.test_match("1X5X2"));
+    }
+
+    #[test]
+    fn test_contains_and_is_disjoint() {
+        let lower_bound = VersionSpec::try_from(">=1.0").unwrap();
+        let narrower = VersionSpec::try_from(">=2.0").unwrap();
+        // Every version satisfying ">=2.0" also satisfies ">=1.0".
+        assert!(lower_bound.contains(&narrower));
+        assert!(!narrower.contains(&lower_bound));
+        assert!(!lower_bound.is_disjoint(&narrower));
+
+        let below = VersionSpec::try_from("<1.0").unwrap();
+        let above = VersionSpec::try_from(">=1.0").unwrap();
+        assert!(below.is_disjoint(&above));
+        assert!(!below.contains(&above));
+
+        // The `!=X.*` form must produce a real interval set, not fall back to comparing
+        // spec strings, so it can be checked for overlap and containment against
+        // differently-worded specs.
+        let excludes_release = VersionSpec::try_from(">=2.7, !=3.0.*, !=3.1.*, !=3.2.*, !=3.3.*").unwrap();
+        let inside_excluded_range = VersionSpec::try_from(">=3.1, <3.2").unwrap();
+        assert!(excludes_release.is_disjoint(&inside_excluded_range));
+        let outside_excluded_range = VersionSpec::try_from(">=2.8, <3.0").unwrap();
+        assert!(excludes_release.contains(&outside_excluded_range));
+    }
 }
\ No newline at end of file
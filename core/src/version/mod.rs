@@ -7,6 +7,7 @@ pub mod test_tools;
 pub mod comp_op;
 pub mod custom_parts;
 pub mod errors;
+pub mod manifest;
 pub mod matching;
 pub mod parsers;
 pub mod spec_trees;
@@ -14,6 +15,7 @@ pub mod version;
 pub mod version_part;
 
 pub use self::comp_op::CompOp;
+pub use self::manifest::VersionManifest;
 pub use self::parsers::conda::conda_parser;
 pub use self::version::Version;
 pub use self::version_part::VersionPart;